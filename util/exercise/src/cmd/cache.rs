@@ -0,0 +1,92 @@
+/// This module contains a small on-disk cache for fetched canonical data.
+///
+/// Canonical data rarely changes between invocations, so `generate_exercise`
+/// can skip the network/FS fetch entirely when a cache entry already exists
+/// under `.cache/` that was written against the problem-specifications
+/// revision currently checked out locally.
+use exercise::Result;
+use serde_json::Value as JsonValue;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn cache_dir() -> PathBuf {
+    Path::new(&*exercise::TRACK_ROOT).join(".cache")
+}
+
+fn cache_path(exercise_name: &str) -> PathBuf {
+    cache_dir().join(format!("{}.json", exercise_name))
+}
+
+// The commit the local problem-specifications clone is checked out at, used
+// to invalidate a cache entry once the canonical data it was built from
+// could have changed upstream. `None` if there's no local clone to check
+// (canonical data was fetched some other way), in which case caching is
+// skipped entirely rather than risk serving stale data forever.
+fn problem_specifications_revision() -> Option<String> {
+    let problem_specifications_path = Path::new(&*exercise::TRACK_ROOT)
+        .join("..")
+        .join("problem-specifications");
+
+    if !problem_specifications_path.exists() {
+        return None;
+    }
+
+    let output = Command::new("git")
+        .current_dir(&problem_specifications_path)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|revision| revision.trim().to_string())
+}
+
+// Return the cached canonical-data.json contents for `exercise_name`, if a
+// cache entry exists and was written against the problem-specifications
+// revision currently checked out. Any I/O error, parse failure or revision
+// mismatch is treated as a cache miss rather than a hard failure.
+pub fn load(exercise_name: &str) -> Option<String> {
+    let current_revision = problem_specifications_revision()?;
+
+    let contents = fs::read_to_string(cache_path(exercise_name)).ok()?;
+    let entry: JsonValue = serde_json::from_str(&contents).ok()?;
+
+    let cached_revision = entry.get("problem_specifications_revision")?.as_str()?;
+
+    if cached_revision != current_revision {
+        return None;
+    }
+
+    entry.get("json")?.as_str().map(str::to_string)
+}
+
+// Persist `json`, the raw canonical-data.json contents, to the cache for
+// `exercise_name`, tagged with the problem-specifications revision it was
+// fetched against. A no-op if that revision can't be determined, since an
+// entry with no revision to invalidate against would never expire.
+pub fn store(exercise_name: &str, json: &str) -> Result<()> {
+    let current_revision = match problem_specifications_revision() {
+        Some(revision) => revision,
+        None => return Ok(()),
+    };
+
+    fs::create_dir_all(cache_dir())?;
+
+    let entry = serde_json::json!({
+        "problem_specifications_revision": current_revision,
+        "json": json,
+    });
+
+    fs::write(cache_path(exercise_name), serde_json::to_string(&entry)?)?;
+
+    Ok(())
+}
@@ -1,4 +1,9 @@
 /// This module contains source for the `generate` command.
+// Declared with an explicit path (rather than a `cmd/mod.rs`/`cmd.rs`, which
+// this checkout doesn't include) so this doesn't collide with however the
+// rest of the tree already owns the `cmd` module.
+#[path = "cache.rs"]
+mod cache;
 use exercise::{self, get, val_as, Result};
 use failure::format_err;
 use serde_json::Value as JsonValue;
@@ -15,6 +20,149 @@ const EXAMPLE_RS_CONTENT: &str = include_str!("defaults/example.rs");
 const DESCRIPTION_MD_CONTENT: &str = include_str!("defaults/description.md");
 const METADATA_YML_CONTENT: &str = include_str!("defaults/metadata.yml");
 
+// Maximum Levenshtein distance (relative to the name length) for a directory
+// or canonical-data slug to be considered a plausible typo
+const SUGGESTION_MAX_DISTANCE: usize = 3;
+
+// Compute the Levenshtein edit distance between two strings
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur: Vec<usize> = vec![0; b.len() + 1];
+
+    for (i, &a_char) in a.iter().enumerate() {
+        cur[0] = i + 1;
+
+        for (j, &b_char) in b.iter().enumerate() {
+            let cost = if a_char != b_char { 1 } else { 0 };
+
+            cur[j + 1] = (prev[j + 1] + 1).min(cur[j] + 1).min(prev[j] + cost);
+        }
+
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[b.len()]
+}
+
+// Collect every exercise slug we know about: directories already present
+// under `exercises/` plus the exercises problem-specifications has canonical
+// data for
+fn known_exercise_names() -> Vec<String> {
+    let mut names = Vec::new();
+
+    let exercises_dir = Path::new(&*exercise::TRACK_ROOT).join("exercises");
+
+    if let Ok(entries) = fs::read_dir(&exercises_dir) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if entry.path().is_dir() {
+                if let Some(name) = entry.file_name().to_str() {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    let problem_specifications_exercises_dir = Path::new(&*exercise::TRACK_ROOT)
+        .join("..")
+        .join("problem-specifications")
+        .join("exercises");
+
+    if let Ok(entries) = fs::read_dir(&problem_specifications_exercises_dir) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if entry.path().is_dir() {
+                if let Some(name) = entry.file_name().to_str() {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+
+    names
+}
+
+// Find the closest known exercise name(s) to `exercise_name`, for use in a
+// "did you mean ...?" hint when the requested name doesn't match anything
+fn suggest_exercise_names(exercise_name: &str) -> Vec<String> {
+    let threshold = SUGGESTION_MAX_DISTANCE.min((exercise_name.len() / 3).max(1));
+
+    let mut suggestions: Vec<(usize, String)> = known_exercise_names()
+        .into_iter()
+        .filter(|name| name != exercise_name)
+        .map(|name| (levenshtein_distance(exercise_name, &name), name))
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+
+    suggestions.sort_by_key(|(distance, _)| *distance);
+
+    if let Some(&(best_distance, _)) = suggestions.first() {
+        suggestions
+            .into_iter()
+            .take_while(|(distance, _)| *distance == best_distance)
+            .map(|(_, name)| name)
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+// Format a "did you mean ...?" hint for the given suggestions, or an empty
+// string if there are none
+fn did_you_mean(exercise_name: &str) -> String {
+    let suggestions = suggest_exercise_names(exercise_name);
+
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" (did you mean {}?)", suggestions.join(", "))
+    }
+}
+
+// Cache `canonical_data` for `exercise_name`. Caching is a pure
+// optimization: a failure to write it shouldn't fail exercise generation.
+fn cache_canonical_data(exercise_name: &str, canonical_data: &JsonValue) {
+    if let Ok(json) = serde_json::to_string(canonical_data) {
+        let _ = cache::store(exercise_name, &json);
+    }
+}
+
+// Fetch the canonical data for `exercise_name`, consulting the on-disk cache
+// first so repeated/bulk regeneration doesn't re-fetch data that hasn't
+// changed. Falls back to `exercise::get_canonical_data` on a cache miss (or
+// a corrupt/stale cache entry) and repopulates the cache with the result.
+fn fetch_canonical_data(exercise_name: &str) -> Result<JsonValue> {
+    if let Some(cached_json) = cache::load(exercise_name) {
+        if let Ok(canonical_data) = serde_json::from_str(&cached_json) {
+            return Ok(canonical_data);
+        }
+    }
+
+    let canonical_data = exercise::get_canonical_data(exercise_name)?;
+
+    cache_canonical_data(exercise_name, &canonical_data);
+
+    Ok(canonical_data)
+}
+
+// Fetch the canonical data for `exercise_name` straight from
+// `exercise::get_canonical_data`, bypassing any cached entry. Used by
+// `update_exercise`, where the whole point of running it is to pick up
+// canonical data that's changed since the cache was last populated -
+// `fetch_canonical_data`'s cache-hit-preferring behavior would otherwise
+// keep serving the stale entry indefinitely.
+fn refresh_canonical_data(exercise_name: &str) -> Result<JsonValue> {
+    let canonical_data = exercise::get_canonical_data(exercise_name)?;
+
+    cache_canonical_data(exercise_name, &canonical_data);
+
+    Ok(canonical_data)
+}
+
 // Generate .meta directory and its contents without using the canonical data
 fn generate_meta(exercise_name: &str, exercise_path: &Path) -> Result<()> {
     let meta_dir = exercise_path.join(".meta");
@@ -66,19 +214,18 @@ fn parse_case(
     Ok(())
 }
 
-// Generate test suite using the canonical data
+// Generate test suite using the canonical data, writing it to `tests_path`
+// (the caller decides whether that's the exercise's real `tests/<name>.rs`
+// or a scratch path to review before it replaces anything). Doesn't touch
+// `Cargo.toml` - callers that need the version bumped call
+// `exercise::update_cargo_toml_version` themselves, at whatever point fits
+// their own review/rollback story.
 fn generate_tests_from_canonical_data(
     exercise_name: &str,
-    exercise_path: &Path,
+    tests_path: &Path,
     canonical_data: &JsonValue,
     use_maplit: bool,
 ) -> Result<()> {
-    exercise::update_cargo_toml_version(exercise_name, canonical_data)?;
-
-    let tests_path = exercise_path
-        .join("tests")
-        .join(format!("{}.rs", exercise_name));
-
     let tests_content = exercise::get_tests_content(exercise_name)?;
 
     let updated_tests_content = format!(
@@ -95,7 +242,7 @@ fn generate_tests_from_canonical_data(
         exercise_name=exercise_name,
     );
 
-    fs::write(&tests_path, updated_tests_content)?;
+    fs::write(tests_path, updated_tests_content)?;
 
     let mut property_functions: HashMap<String, String> = HashMap::new();
 
@@ -116,7 +263,7 @@ fn generate_tests_from_canonical_data(
         test_functions.insert(0, first_test_function);
     }
 
-    let mut tests_file = OpenOptions::new().append(true).open(&tests_path)?;
+    let mut tests_file = OpenOptions::new().append(true).open(tests_path)?;
 
     for property_body in property_functions.values() {
         tests_file.write_all(property_body.as_bytes())?;
@@ -124,7 +271,7 @@ fn generate_tests_from_canonical_data(
 
     tests_file.write_all(test_functions.join("\n\n").as_bytes())?;
 
-    exercise::rustfmt(&tests_path)?;
+    exercise::rustfmt(tests_path)?;
 
     Ok(())
 }
@@ -175,7 +322,12 @@ fn generate_readme(exercise_name: &str) -> Result<()> {
 // Generate a new exercise with specified name and flags
 pub fn generate_exercise(exercise_name: &str, use_maplit: bool) -> Result<()> {
     if exercise::exercise_exists(exercise_name) {
-        return Err(format_err!("exercise with the name {} already exists", exercise_name,).into());
+        return Err(format_err!(
+            "exercise with the name {} already exists{}",
+            exercise_name,
+            did_you_mean(exercise_name),
+        )
+        .into());
     }
 
     let exercise_path = Path::new(&*exercise::TRACK_ROOT)
@@ -229,21 +381,28 @@ pub fn generate_exercise(exercise_name: &str, use_maplit: bool) -> Result<()> {
 
     fs::write(exercise_path.join("example.rs"), EXAMPLE_RS_CONTENT)?;
 
-    match exercise::get_canonical_data(exercise_name) {
+    match fetch_canonical_data(exercise_name) {
         Ok(canonical_data) => {
             println!("Generating tests from canonical data");
 
+            exercise::update_cargo_toml_version(exercise_name, &canonical_data)?;
+
+            let tests_path = exercise_path
+                .join("tests")
+                .join(format!("{}.rs", exercise_name));
+
             generate_tests_from_canonical_data(
                 &exercise_name,
-                &exercise_path,
+                &tests_path,
                 &canonical_data,
                 use_maplit,
             )?;
         }
         Err(_) => {
             println!(
-                "No canonical data for exercise '{}' found. Generating standard exercise template.",
-                &exercise_name
+                "No canonical data for exercise '{}' found{}. Generating standard exercise template.",
+                &exercise_name,
+                did_you_mean(exercise_name),
             );
 
             test_file.write_all(b"// Add your tests here\n")?;
@@ -253,5 +412,124 @@ pub fn generate_exercise(exercise_name: &str, use_maplit: bool) -> Result<()> {
     generate_meta(&exercise_name, &exercise_path)?;
     generate_readme(&exercise_name)?;
 
+    Ok(())
+}
+
+// Map test function name -> its full source (attributes, signature and
+// body) for every top-level `fn` in a generated tests file
+fn test_functions_by_name(content: &str) -> HashMap<String, String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut functions = HashMap::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let trimmed = lines[i].trim_start();
+
+        if trimmed.starts_with("fn ") || trimmed.starts_with("pub fn ") {
+            let mut attrs_start = i;
+
+            while attrs_start > 0 && lines[attrs_start - 1].trim_start().starts_with('#') {
+                attrs_start -= 1;
+            }
+
+            let name = trimmed
+                .trim_start_matches("pub ")
+                .trim_start_matches("fn ")
+                .split(|c: char| c == '(' || c.is_whitespace())
+                .next()
+                .unwrap_or("")
+                .to_string();
+
+            let mut end = i;
+
+            while end < lines.len() - 1 && lines[end].trim_end() != "}" {
+                end += 1;
+            }
+
+            if !name.is_empty() {
+                functions.insert(name, lines[attrs_start..=end].join("\n"));
+            }
+
+            i = end + 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    functions
+}
+
+// Print which test functions a regenerated tests file adds, removes, or
+// changes (e.g. a flipped `#[ignore]` or an updated expected value)
+// relative to the previous version, so the maintainer can review the
+// update before it overwrites anything
+fn print_test_function_diff(previous_content: &str, updated_content: &str) {
+    let previous = test_functions_by_name(previous_content);
+    let updated = test_functions_by_name(updated_content);
+
+    let mut names: Vec<&String> = previous.keys().chain(updated.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        match (previous.get(name), updated.get(name)) {
+            (None, Some(_)) => println!("  + {}", name),
+            (Some(_), None) => println!("  - {}", name),
+            (Some(old), Some(new)) if old != new => println!("  ~ {}", name),
+            _ => {}
+        }
+    }
+}
+
+// Regenerate an existing exercise's `tests/<name>.rs` from the latest
+// canonical data, preserving the hand-written `example.rs` and any custom
+// `.meta` files (neither is touched by `generate_tests_from_canonical_data`).
+// The regenerated file is written to a scratch path first and diffed
+// function-by-function against the current one; `tests_path` itself is only
+// overwritten once that diff has been printed, so the previous file is
+// still on disk for a maintainer to fall back to while reviewing it. The
+// `Cargo.toml` version bump is held until after that same diff, so nothing
+// is touched until the maintainer has had a chance to see what changed.
+pub fn update_exercise(exercise_name: &str, use_maplit: bool) -> Result<()> {
+    if !exercise::exercise_exists(exercise_name) {
+        return Err(format_err!(
+            "exercise with the name {} does not exist{}",
+            exercise_name,
+            did_you_mean(exercise_name),
+        )
+        .into());
+    }
+
+    let exercise_path = Path::new(&*exercise::TRACK_ROOT)
+        .join("exercises")
+        .join(exercise_name);
+
+    let tests_path = exercise_path
+        .join("tests")
+        .join(format!("{}.rs", exercise_name));
+
+    let scratch_tests_path = tests_path.with_file_name(format!("{}.rs.new", exercise_name));
+
+    let previous_tests_content = fs::read_to_string(&tests_path).unwrap_or_default();
+
+    let canonical_data = refresh_canonical_data(exercise_name)?;
+
+    println!("Regenerating tests for {} from canonical data", exercise_name);
+
+    generate_tests_from_canonical_data(
+        exercise_name,
+        &scratch_tests_path,
+        &canonical_data,
+        use_maplit,
+    )?;
+
+    let updated_tests_content = fs::read_to_string(&scratch_tests_path)?;
+
+    print_test_function_diff(&previous_tests_content, &updated_tests_content);
+
+    exercise::update_cargo_toml_version(exercise_name, &canonical_data)?;
+
+    fs::rename(&scratch_tests_path, &tests_path)?;
+
     Ok(())
 }
\ No newline at end of file